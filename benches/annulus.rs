@@ -0,0 +1,34 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use math::dim2::Vec2;
+use noise::distributions::AnnulusDistribution;
+use rand::distributions::Distribution;
+
+/// Compares the polar method against bounding-square rejection across a range of inner/outer
+/// radius ratios, to empirically locate the crossover where one becomes faster than the other
+/// (see `AnnulusDistribution::rejection_crossover`).
+fn bench_annulus(c: &mut Criterion) {
+    let mut group = c.benchmark_group("annulus");
+    let mut rng = rand::thread_rng();
+
+    for &ratio in &[0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.8] {
+        let high = 1.0;
+        let low = ratio * high;
+
+        let mut polar = AnnulusDistribution::<2, Vec2>::new(low, high);
+        polar.rejection_crossover = 0.0; // force polar
+        group.bench_with_input(BenchmarkId::new("polar", ratio), &ratio, |b, _| {
+            b.iter(|| polar.sample(&mut rng))
+        });
+
+        let mut rejection = AnnulusDistribution::<2, Vec2>::new(low, high);
+        rejection.rejection_crossover = 1.0; // force rejection
+        group.bench_with_input(BenchmarkId::new("rejection", ratio), &ratio, |b, _| {
+            b.iter(|| rejection.sample(&mut rng))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_annulus);
+criterion_main!(benches);
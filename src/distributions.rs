@@ -1,31 +1,116 @@
-use math::dim2::Vec2;
-
-/// Adapts  https://mathworld.wolfram.com/DiskPointPicking.html to an annulus
-/// For a disk, the rejection method is faster, but thinner the annulus is, slower the rejection
-/// method is
-/// for an annulus of inner radius r, outer radius 0.5, the rejection method has a probability of
-/// pi/4 - pi*r^2
-/// thus the expected number samples needed of is 1/ pi / (1/4 - r^2) -> infty when r -> 1/2
-/// Maybe do a split at r = 0.3 (expected samples count ~= 2)?
-/// Need a benchmark for that
-pub struct AnnulusDistribution {
-    radius: rand::distributions::Uniform<f32>,
+use math::Vector;
+
+/// Adapts  https://mathworld.wolfram.com/DiskPointPicking.html to an annulus, generalized to an
+/// N-dimensional spherical shell.
+///
+/// For N = 2, two methods are available: the polar method below, and rejection sampling from the
+/// bounding square, which is faster once the annulus is "fat" (small inner radius) - see
+/// `rejection_crossover`. For N != 2, a direction is drawn by sampling `N` independent
+/// standard-normal values and normalizing the resulting vector, which is uniform on the unit
+/// N-sphere; the radius is then drawn with `r = (U * (high^N - low^N) + low^N)^(1/N)`, which keeps
+/// points uniform by volume in the shell (for N = 2 this reduces to the polar method's `sqrt`
+/// trick).
+pub struct AnnulusDistribution<const N: usize, V: Vector<N>> {
+    radius_pow: rand::distributions::Uniform<f32>,
+    inv_n: f32,
+    low: f32,
+    high: f32,
     angle: rand::distributions::Uniform<f32>,
+    square: rand::distributions::Uniform<f32>,
+    /// Below this inner/outer radius ratio, rejection sampling from the bounding square beats the
+    /// polar method; see the `annulus` Criterion benchmark for how this constant was chosen
+    /// (empirically, the crossover sits around 0.3).
+    pub rejection_crossover: f32,
+    _marker: std::marker::PhantomData<V>,
 }
 
-impl AnnulusDistribution {
+impl<const N: usize, V: Vector<N>> AnnulusDistribution<N, V> {
     pub fn new(low: f32, high: f32) -> Self {
         Self {
+            radius_pow: rand::distributions::Uniform::<f32>::new_inclusive(
+                low.powi(N as i32),
+                high.powi(N as i32),
+            ),
+            inv_n: 1.0 / N as f32,
+            low,
+            high,
             angle: rand::distributions::Uniform::<f32>::new_inclusive(0.0, std::f32::consts::TAU),
-            radius: rand::distributions::Uniform::<f32>::new_inclusive(low * low, high * high),
+            square: rand::distributions::Uniform::<f32>::new_inclusive(-high, high),
+            rejection_crossover: 0.3,
+            _marker: std::marker::PhantomData,
         }
     }
-}
 
-impl rand::distributions::Distribution<Vec2> for AnnulusDistribution {
-    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
-        let r = self.radius.sample(rng).sqrt();
+    fn sample_direction<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> [f32; N] {
+        // Box-Muller, consumed two components at a time; for an odd N the last draw only keeps
+        // its first component.
+        let mut components = [0.0f32; N];
+        let mut i = 0;
+        while i < N {
+            let u1: f32 = rng.gen::<f32>().max(f32::MIN_POSITIVE);
+            let u2: f32 = rng.gen();
+            let r = (-2.0 * u1.ln()).sqrt();
+            let (sin, cos) = (std::f32::consts::TAU * u2).sin_cos();
+            components[i] = r * cos;
+            i += 1;
+            if i < N {
+                components[i] = r * sin;
+                i += 1;
+            }
+        }
+
+        let length: f32 = components.iter().map(|c| c * c).sum::<f32>().sqrt();
+        components.map(|c| c / length)
+    }
+
+    /// Uniform-in-volume sampling for the general N-dimensional shell.
+    fn sample_general<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> V {
+        let r = self.radius_pow.sample(rng).powf(self.inv_n);
+        let direction = self.sample_direction(rng);
+        V::from_array(direction.map(|c| c * r))
+    }
+
+    /// N = 2 polar method: draw an angle and a radius whose square is uniform between the two
+    /// squared bounds, so the point stays uniform in the annulus's area.
+    fn sample_polar<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> V {
+        let r = self.radius_pow.sample(rng).sqrt();
         let (sin, cos) = self.angle.sample(rng).sin_cos();
-        Vec2::from_components(r * cos, r * sin)
+        let mut components = [0.0f32; N];
+        components[0] = r * cos;
+        components[1] = r * sin;
+        V::from_array(components)
+    }
+
+    /// N = 2 rejection method: draw uniformly in the bounding square and reject until the point
+    /// lands in the annulus. Faster than the polar method for fat annuli, where few draws are
+    /// wasted outside the inner hole.
+    fn sample_rejection<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> V {
+        loop {
+            let x = self.square.sample(rng);
+            let y = self.square.sample(rng);
+            let length_squared = x * x + y * y;
+
+            if length_squared >= self.low * self.low && length_squared <= self.high * self.high {
+                let mut components = [0.0f32; N];
+                components[0] = x;
+                components[1] = y;
+                return V::from_array(components);
+            }
+        }
+    }
+}
+
+impl<const N: usize, V: Vector<N>> rand::distributions::Distribution<V>
+    for AnnulusDistribution<N, V>
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> V {
+        if N == 2 {
+            if self.low / self.high < self.rejection_crossover {
+                return self.sample_rejection(rng);
+            }
+            return self.sample_polar(rng);
+        }
+
+        self.sample_general(rng)
     }
 }
@@ -2,101 +2,262 @@ use math::{dim2::Vec2, Vector};
 use rand::prelude::Distribution;
 
 use crate::distributions;
-pub trait PoissonDiskAlgorithm {
+pub trait PoissonDiskAlgorithm<const N: usize, V: Vector<N>> {
     /// Shall be call only once
-    fn init<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2>;
+    fn init<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<V>;
 
     /// Can be called as long None is not returned;
     /// Same semantic as next iter_next
-    fn next<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2>;
+    fn next<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<V>;
+
+    /// An upper bound on how many more samples this algorithm could still produce. The background
+    /// grid holds at most one sample per cell, so `grid cells - samples placed so far` is always
+    /// a valid (if loose) bound.
+    fn remaining_upper_bound(&self) -> usize;
+}
+
+/// The boundary behavior of a sampling domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Domain {
+    /// The domain is a plain rectangle: samples near an edge are unaffected by anything past it.
+    Clamped,
+    /// The domain wraps around on itself, so that samples tile seamlessly: a sample near one edge
+    /// also repels samples near the opposite edge, as if the rectangle were laid side by side
+    /// with copies of itself.
+    Periodic,
 }
 
-struct Grid {
-    inner: Vec<Option<Vec2>>,
-    extent: [usize; 2],
+struct Grid<const N: usize, V: Vector<N>> {
+    inner: Vec<Option<V>>,
+    extent: [usize; N],
+    strides: [usize; N],
     cell_size: f32,
     radius: f32,
-    bottom_left: Vec2,
-    top_right: Vec2,
+    bottom_left: V,
+    top_right: V,
+    domain_size: [f32; N],
+    domain: Domain,
 }
 
-impl Grid {
-    fn new(radius: f32, extent: [Vec2; 2]) -> Self {
-        let cell_size = radius / f32::sqrt(2.0);
+impl<const N: usize, V: Vector<N>> Grid<N, V> {
+    fn new(radius: f32, extent: [V; 2], domain: Domain) -> Self {
+        // Cell size is chosen so that a cell's diagonal never exceeds `radius`, guaranteeing at
+        // most one sample per cell.
+        let cell_size = radius / f32::sqrt(N as f32);
+
+        let lo = extent[0].into_array();
+        let hi = extent[1].into_array();
 
-        let [x1, y1] = extent[0].into_array();
-        let [x2, y2] = extent[1].into_array();
+        let mut dims = [0usize; N];
+        let mut domain_size = [0.0f32; N];
+        for i in 0..N {
+            domain_size[i] = hi[i] - lo[i];
+            dims[i] = (domain_size[i] / cell_size).ceil() as usize;
+        }
+
+        let mut strides = [1usize; N];
+        for i in 1..N {
+            strides[i] = strides[i - 1] * dims[i - 1];
+        }
 
-        let grid_width = x2 - x1;
-        let grid_height = y2 - y1;
+        let cells = dims.iter().product();
 
-        let width = (grid_width / cell_size).ceil() as usize;
-        let height = (grid_height / cell_size).ceil() as usize;
         Self {
-            inner: vec![None; width * height],
+            inner: vec![None; cells],
             bottom_left: extent[0],
             top_right: extent[1],
-            extent: [width, height],
+            extent: dims,
+            strides,
             cell_size,
             radius,
+            domain_size,
+            domain,
         }
     }
 
-    fn get_cell(&self, pos: Vec2) -> Option<(usize, usize)> {
-        let [x, y] = pos.into_array();
-        let [bx, by] = self.bottom_left.into_array();
-        let [tx, ty] = self.top_right.into_array();
+    fn cells(&self) -> usize {
+        self.inner.len()
+    }
 
-        if x >= tx || x <= bx || y >= ty || y <= by {
-            return None;
-        }
+    fn cell_index(&self, cell: [usize; N]) -> usize {
+        (0..N).map(|i| cell[i] * self.strides[i]).sum()
+    }
+
+    fn get_cell(&self, pos: V) -> Option<[usize; N]> {
+        let p = pos.into_array();
+        let lo = self.bottom_left.into_array();
+        let hi = self.top_right.into_array();
+
+        let mut cell = [0usize; N];
+        for i in 0..N {
+            let mut coord = p[i];
+            if self.domain == Domain::Periodic {
+                // Wrap the candidate back into the domain before indexing, modulo its extent, so
+                // a point just past one edge is treated as the seam it actually sits on.
+                coord = lo[i] + (coord - lo[i]).rem_euclid(self.domain_size[i]);
+            } else if coord >= hi[i] || coord <= lo[i] {
+                return None;
+            }
 
-        let [base_x, base_y] = self.bottom_left.into_array();
+            let index = ((coord - lo[i]) / self.cell_size).floor() as usize;
+            cell[i] = index.min(self.extent[i] - 1);
+        }
 
-        let w = ((x - base_x) / self.cell_size).floor();
-        let h = ((y - base_y) / self.cell_size).floor();
+        Some(cell)
+    }
 
-        Some((w as usize, h as usize))
+    fn get_index(&self, pos: V) -> Option<usize> {
+        self.get_cell(pos).map(|cell| self.cell_index(cell))
     }
 
-    fn get_index(&self, pos: Vec2) -> Option<usize> {
-        self.get_cell(pos).map(|(w, h)| w + h * self.extent[0])
+    /// Wraps `pos` back into `[bottom_left, top_right)` under [`Domain::Periodic`]; a no-op under
+    /// [`Domain::Clamped`]. Candidates that land outside the domain (e.g. an annulus offset from
+    /// a sample near an edge) must be normalized with this before being inserted or returned, so
+    /// stored and yielded positions always stay inside the rectangle.
+    fn wrap(&self, pos: V) -> V {
+        if self.domain != Domain::Periodic {
+            return pos;
+        }
+
+        let p = pos.into_array();
+        let lo = self.bottom_left.into_array();
+        V::from_array(std::array::from_fn(|i| {
+            lo[i] + (p[i] - lo[i]).rem_euclid(self.domain_size[i])
+        }))
     }
 
-    fn insert(&mut self, pos: Vec2) -> Option<usize> {
+    fn insert(&mut self, pos: V) -> Option<usize> {
         self.get_index(pos).map(|index| {
             self.inner[index] = Some(pos);
             index
         })
     }
 
-    fn get(&self, index: usize) -> Option<Vec2> {
+    fn get(&self, index: usize) -> Option<V> {
         self.inner.get(index).and_then(|x| *x)
     }
 
-    fn can_insert(&self, x: Vec2) -> bool {
-        let m = 2 * ((1.0 / self.cell_size).ceil() as isize + 1);
-        let Some((w, h)) = self.get_cell(x) else {return false};
+    /// The lower corner of the given cell.
+    fn cell_corner(&self, cell: [usize; N]) -> V {
+        let lo = self.bottom_left.into_array();
+        V::from_array(std::array::from_fn(|i| lo[i] + cell[i] as f32 * self.cell_size))
+    }
 
-        for i in -m..=m {
-            let w = w as isize + i;
-            if w < 0 {
-                continue;
+    /// The cell's bounds, clamped to the domain: the grid is sized by rounding the domain extent
+    /// up to a whole number of cells, so a border cell's far corner can land at or past
+    /// `top_right`. Callers sampling or probing inside a cell must use this instead of
+    /// `cell_corner() + cell_size`, or they'll draw points outside the domain that can never be
+    /// inserted.
+    fn cell_bounds(&self, cell: [usize; N]) -> (V, V) {
+        let lo = self.cell_corner(cell).into_array();
+        let hi_bound = self.top_right.into_array();
+        let hi = std::array::from_fn(|i| (lo[i] + self.cell_size).min(hi_bound[i]));
+        (V::from_array(lo), V::from_array(hi))
+    }
+
+    /// How far (in cells, along a single axis) a sample can possibly be from another sample
+    /// within `radius` of it, given `cell_size = radius / sqrt(N)`. A sample can sit anywhere in
+    /// its cell, so two conflicting samples can be up to `1 + radius / cell_size = 1 + sqrt(N)`
+    /// cells apart; round up and drop the fencepost to get the search window. Under
+    /// [`Domain::Periodic`] the window is one cell wider: the last cell along an axis is cut short
+    /// by the domain edge, so across the seam two samples can be one more cell apart in index.
+    fn neighbor_window(&self) -> isize {
+        let window = ((N as f32).sqrt() + 1.0).ceil() as isize - 1;
+        if self.domain == Domain::Periodic {
+            window + 1
+        } else {
+            window
+        }
+    }
+
+    /// All cell offsets within +/-[`Self::neighbor_window`] along every axis, i.e. the
+    /// neighborhood that can possibly contain a sample within `radius` of a cell's content.
+    fn neighbor_offsets(&self) -> Vec<[isize; N]> {
+        let window = self.neighbor_window();
+        let mut offsets = vec![[0isize; N]];
+        for axis in 0..N {
+            let mut next = Vec::with_capacity(offsets.len() * (2 * window as usize + 1));
+            for offset in &offsets {
+                for d in -window..=window {
+                    let mut o = *offset;
+                    o[axis] = d;
+                    next.push(o);
+                }
             }
+            offsets = next;
+        }
+        offsets
+    }
 
-            for j in -m..=m {
-                let h = h as isize + j;
-                if h < 0 {
-                    continue;
+    /// Squared distance between `a` and `b`, using the minimum-image convention under
+    /// [`Domain::Periodic`]: each component of `a - b` is wrapped into `[-size/2, +size/2]` so
+    /// that points near opposite edges of the domain correctly repel each other across the seam.
+    fn distance_squared(&self, a: V, b: V) -> f32 {
+        let pa = a.into_array();
+        let pb = b.into_array();
+
+        (0..N)
+            .map(|i| {
+                let mut d = pa[i] - pb[i];
+                if self.domain == Domain::Periodic {
+                    let size = self.domain_size[i];
+                    d -= size * (d / size).round();
                 }
+                d * d
+            })
+            .sum()
+    }
+
+    /// Sound (but not complete) test that every point within `half_diagonal` of `center` is
+    /// within `radius` of *some single* placed sample: by the triangle inequality, a sample `s`
+    /// with `distance(center, s) + half_diagonal <= radius` covers the whole ball, and hence the
+    /// whole region around `center`. A region can still be fully covered by the union of several
+    /// samples without any single one passing this test, in which case this conservatively
+    /// returns `false` (not covered) and the caller should subdivide further.
+    fn is_region_covered(&self, center: V, half_diagonal: f32) -> bool {
+        self.inner
+            .iter()
+            .flatten()
+            .any(|&sample| self.distance_squared(center, sample).sqrt() + half_diagonal <= self.radius)
+    }
+
+    /// Sound test that no point within `half_diagonal` of `center` is within `radius` of any
+    /// placed sample: by the triangle inequality, if every sample `s` has
+    /// `distance(center, s) - half_diagonal > radius`, no point in that region can be within
+    /// `radius` of `s` either.
+    fn is_region_free(&self, center: V, half_diagonal: f32) -> bool {
+        self.inner
+            .iter()
+            .flatten()
+            .all(|&sample| self.distance_squared(center, sample).sqrt() - half_diagonal > self.radius)
+    }
+
+    fn can_insert(&self, x: V) -> bool {
+        let Some(cell) = self.get_cell(x) else {
+            return false;
+        };
+
+        for offset in self.neighbor_offsets() {
+            let mut neighbor = [0usize; N];
+            let mut in_bounds = true;
+            for i in 0..N {
+                let mut c = cell[i] as isize + offset[i];
+                if self.domain == Domain::Periodic {
+                    c = c.rem_euclid(self.extent[i] as isize);
+                } else if c < 0 || c as usize >= self.extent[i] {
+                    in_bounds = false;
+                    break;
+                }
+                neighbor[i] = c as usize;
+            }
 
-                let index = h as usize * self.extent[0] + w as usize;
-                if let Some(pos) = self.get(index) {
-                    let length = (pos - x).length_squared();
+            if !in_bounds {
+                continue;
+            }
 
-                    if length <= self.radius * self.radius {
-                        return false;
-                    }
+            if let Some(pos) = self.get(self.cell_index(neighbor)) {
+                if self.distance_squared(pos, x) <= self.radius * self.radius {
+                    return false;
                 }
             }
         }
@@ -106,37 +267,49 @@ impl Grid {
 }
 
 /// See https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph07-poissondisk.pdf
-pub struct RobertBridson {
-    grid: Grid,
+pub struct RobertBridson<const N: usize, V: Vector<N>> {
+    grid: Grid<N, V>,
     indices: Vec<usize>,
-    annulus_distr: distributions::AnnulusDistribution,
-    vec2_distr: rand::distributions::Uniform<Vec2>,
+    annulus_distr: distributions::AnnulusDistribution<N, V>,
+    component_distrs: [rand::distributions::Uniform<f32>; N],
+    placed: usize,
 }
 
-impl PoissonDiskAlgorithm for RobertBridson {
-    fn init<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2> {
-        let x0 = self.vec2_distr.sample(rng);
+impl<const N: usize, V: Vector<N>> PoissonDiskAlgorithm<N, V> for RobertBridson<N, V> {
+    fn init<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<V> {
+        let mut components = [0.0f32; N];
+        for i in 0..N {
+            components[i] = self.component_distrs[i].sample(rng);
+        }
+        let x0 = V::from_array(components);
 
-        //TODO: implement - in math lib
         let index = self.grid.insert(x0).unwrap();
         self.indices.push(index);
+        self.placed += 1;
         Some(x0)
     }
 
-    fn next<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2> {
+    fn next<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<V> {
         loop {
             if self.indices.is_empty() {
                 return None;
             }
 
             let index = rng.gen_range(0..self.indices.len());
-            let Some(xi) = self.grid.get(self.indices[index]) else {panic!()};
+            let Some(xi) = self.grid.get(self.indices[index]) else {
+                panic!()
+            };
 
             for _ in 0..30 {
-                let x = xi + self.annulus_distr.sample(rng);
+                let offset: V = self.annulus_distr.sample(rng);
+                let xi_arr = xi.into_array();
+                let offset_arr = offset.into_array();
+                let x = V::from_array(std::array::from_fn(|i| xi_arr[i] + offset_arr[i]));
+                let x = self.grid.wrap(x);
 
                 if self.grid.can_insert(x) {
                     self.indices.push(self.grid.insert(x).unwrap());
+                    self.placed += 1;
 
                     return Some(x);
                 }
@@ -144,48 +317,291 @@ impl PoissonDiskAlgorithm for RobertBridson {
             self.indices.swap_remove(index);
         }
     }
+
+    fn remaining_upper_bound(&self) -> usize {
+        self.grid.cells() - self.placed
+    }
 }
 
-impl RobertBridson {
-    pub fn new(radius: f32, extent: [Vec2; 2]) -> Self {
+impl<const N: usize, V: Vector<N>> RobertBridson<N, V> {
+    pub fn new(radius: f32, extent: [V; 2], domain: Domain) -> Self {
+        let lo = extent[0].into_array();
+        let hi = extent[1].into_array();
+        let component_distrs =
+            std::array::from_fn(|i| rand::distributions::Uniform::<f32>::new(lo[i], hi[i]));
+
         Self {
-            grid: Grid::new(radius, extent),
+            grid: Grid::new(radius, extent, domain),
             indices: vec![],
             annulus_distr: distributions::AnnulusDistribution::new(radius, 2.0 * radius),
-            vec2_distr: rand::distributions::Uniform::<Vec2>::new(extent[0], extent[1]),
+            component_distrs,
+            placed: 0,
         }
     }
 }
 
-pub struct PoissonDisk<R: rand::Rng, T: PoissonDiskAlgorithm> {
+pub struct PoissonDisk<R: rand::Rng, const N: usize, V: Vector<N>, T: PoissonDiskAlgorithm<N, V>> {
     rng: R,
     poisson_algo: T,
     init: bool,
+    _marker: std::marker::PhantomData<V>,
 }
 
-impl<R: rand::Rng, T: PoissonDiskAlgorithm> PoissonDisk<R, T> {
+impl<R: rand::Rng, const N: usize, V: Vector<N>, T: PoissonDiskAlgorithm<N, V>>
+    PoissonDisk<R, N, V, T>
+{
     pub fn new(rng: R, poisson_algo: T) -> Self {
         Self {
             rng,
             poisson_algo,
             init: false,
+            _marker: std::marker::PhantomData,
         }
     }
 }
 
-impl<R: rand::Rng, T: PoissonDiskAlgorithm> Iterator for PoissonDisk<R, T> {
-    type Item = Vec2;
+impl<R: rand::Rng, const N: usize, V: Vector<N>, T: PoissonDiskAlgorithm<N, V>> Iterator
+    for PoissonDisk<R, N, V, T>
+{
+    type Item = V;
 
-    fn next(&mut self) -> Option<Vec2> {
+    fn next(&mut self) -> Option<V> {
         if !self.init {
             self.init = true;
             return self.poisson_algo.init(&mut self.rng);
         }
         self.poisson_algo.next(&mut self.rng)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.poisson_algo.remaining_upper_bound()))
+    }
+}
+
+/// Below this many active cells, switch from batched dart-throwing (Phase 1) to per-cell
+/// exhaustive sampling (Phase 2): tracking and pruning the active list no longer pays off once
+/// there are only a handful left.
+const EBEIDA_PHASE2_THRESHOLD: usize = 16;
+
+/// Candidate darts thrown across a batch of active cells before re-checking which ones are fully
+/// covered, in Phase 1.
+const EBEIDA_DARTS_PER_ROUND: usize = 4;
+
+/// Phase 2's recursion bottoms out once a void region's half diagonal shrinks to this fraction of
+/// `radius`; a region that still can't be certified covered or free at that size is treated as
+/// covered. This is the only source of non-maximality left in Phase 2: it bounds recursion depth
+/// (and hence guarantees termination) at the cost of potentially missing a void smaller than
+/// `radius * EBEIDA_MIN_VOID_SIZE_RATIO`.
+const EBEIDA_MIN_VOID_SIZE_RATIO: f32 = 1e-3;
+
+/// Ebeida et al.'s Poisson-disk sampling: like [`RobertBridson`] but additionally fills the voids
+/// left in the grid cells it still tracks as active, by throwing darts into those cells and, once
+/// few remain, recursively clipping each one's void region down to a small resolution limit.
+///
+/// See "A Simple Algorithm for Maximal Poisson-Disk Sampling in High Dimensions" (Ebeida et al.,
+/// 2012). Phase 1 throws darts into randomly chosen active cells and prunes a cell once a coarse
+/// probe (`is_cell_covered`) suggests it is fully covered by existing samples - a heuristic: a
+/// coarse probe can miss a small leftover void and prune a cell too early, and a pruned cell is
+/// not visited again, so the result is not guaranteed maximal.
+/// Once few active cells remain (or Phase 1 stalls, see [`EBEIDA_PHASE2_THRESHOLD`]), Phase 2
+/// recursively subdivides each remaining cell (`sample_void`), at every step either certifying the
+/// sub-region as fully covered (skip it), certifying it as fully free (sample directly inside it),
+/// or splitting it into quadrants and recursing - down to `EBEIDA_MIN_VOID_SIZE_RATIO * radius`,
+/// below which an unresolved region is conservatively treated as covered. This makes Phase 2
+/// itself maximal up to that resolution limit, which is Phase 2's only gap versus the original
+/// algorithm's exact polygonal clipping.
+pub struct Ebeida {
+    grid: Grid<2, Vec2>,
+    active: Vec<[usize; 2]>,
+    phase2: bool,
+    placed: usize,
+}
+
+impl Ebeida {
+    pub fn new(radius: f32, extent: [Vec2; 2]) -> Self {
+        let grid = Grid::new(radius, extent, Domain::Clamped);
+        let active = (0..grid.extent[0])
+            .flat_map(|w| (0..grid.extent[1]).map(move |h| [w, h]))
+            .collect();
+
+        Self {
+            grid,
+            active,
+            phase2: false,
+            placed: 0,
+        }
+    }
+
+    fn sample_in_cell<R: rand::Rng + ?Sized>(&self, cell: [usize; 2], rng: &mut R) -> Vec2 {
+        let (min, max) = self.grid.cell_bounds(cell);
+        let [x0, y0] = min.into_array();
+        let [x1, y1] = max.into_array();
+        Vec2::from_components(rng.gen_range(x0..x1), rng.gen_range(y0..y1))
+    }
+
+    /// Heuristic coverage test: checks a handful of probe points (the cell's corners and center)
+    /// rather than the exact void region, so it can return `true` for a cell that still has a
+    /// placeable - if small and off-probe - sub-region. See the type-level doc.
+    fn is_cell_covered(&self, cell: [usize; 2]) -> bool {
+        let (min, max) = self.grid.cell_bounds(cell);
+        let [x0, y0] = min.into_array();
+        let [x1, y1] = max.into_array();
+
+        [(x0, y0), (x1, y0), (x0, y1), (x1, y1), ((x0 + x1) / 2.0, (y0 + y1) / 2.0)]
+            .into_iter()
+            .all(|(x, y)| !self.grid.can_insert(Vec2::from_components(x, y)))
+    }
+
+    fn prune_covered_cells(&mut self) {
+        self.active.retain(|&cell| !self.is_cell_covered(cell));
+    }
+
+    fn throw_phase1<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2> {
+        while self.active.len() >= EBEIDA_PHASE2_THRESHOLD {
+            let before = self.active.len();
+
+            for _ in 0..EBEIDA_DARTS_PER_ROUND {
+                let cell = self.active[rng.gen_range(0..self.active.len())];
+                let dart = self.sample_in_cell(cell, rng);
+
+                if self.grid.can_insert(dart) {
+                    self.grid.insert(dart);
+                    self.placed += 1;
+                    self.prune_covered_cells();
+                    return Some(dart);
+                }
+            }
+
+            self.prune_covered_cells();
+
+            if self.active.len() == before {
+                // A full round of darts landed nowhere and pruning found nothing newly covered:
+                // the remaining active cells likely have small, hard-to-hit voids, and further
+                // rounds are no more likely to make progress. Hand them off to Phase 2's recursive
+                // clipping instead of spinning here indefinitely.
+                break;
+            }
+        }
+
+        None
+    }
+
+    /// Recursively clips `[min, max]` to the region not yet covered by any placed sample, and
+    /// returns a point drawn from it, or `None` once the whole region is certified covered.
+    ///
+    /// At each step the region is either certified fully covered (`is_region_covered`, prune),
+    /// certified fully free (`is_region_free`, sample anywhere inside it), or - when neither holds
+    /// - split into quadrants and resolved recursively. This bottoms out once the region's half
+    /// diagonal shrinks to `EBEIDA_MIN_VOID_SIZE_RATIO * radius`, at which point an unresolved
+    /// region is conservatively treated as covered; that is the only source of non-maximality
+    /// within Phase 2 (see the type-level doc).
+    fn sample_void<R: rand::Rng + ?Sized>(&self, min: Vec2, max: Vec2, rng: &mut R) -> Option<Vec2> {
+        let [x0, y0] = min.into_array();
+        let [x1, y1] = max.into_array();
+        let center = Vec2::from_components((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+        let half_diagonal = ((x1 - x0).powi(2) + (y1 - y0).powi(2)).sqrt() / 2.0;
+
+        if self.grid.is_region_covered(center, half_diagonal) {
+            return None;
+        }
+
+        if self.grid.is_region_free(center, half_diagonal) {
+            return Some(Vec2::from_components(rng.gen_range(x0..x1), rng.gen_range(y0..y1)));
+        }
+
+        if half_diagonal <= EBEIDA_MIN_VOID_SIZE_RATIO * self.grid.radius {
+            return None;
+        }
+
+        let mid_x = (x0 + x1) / 2.0;
+        let mid_y = (y0 + y1) / 2.0;
+        let quadrants = [
+            (Vec2::from_components(x0, y0), Vec2::from_components(mid_x, mid_y)),
+            (Vec2::from_components(mid_x, y0), Vec2::from_components(x1, mid_y)),
+            (Vec2::from_components(x0, mid_y), Vec2::from_components(mid_x, y1)),
+            (Vec2::from_components(mid_x, mid_y), Vec2::from_components(x1, y1)),
+        ];
+
+        // Visit the quadrants in random order so that repeated calls into an already-partially-
+        // sampled cell don't keep re-deriving darts from the same corner first.
+        let mut order = [0usize, 1, 2, 3];
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.gen_range(0..=i));
+        }
+
+        order
+            .into_iter()
+            .find_map(|i| self.sample_void(quadrants[i].0, quadrants[i].1, rng))
+    }
+
+    fn throw_phase2<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2> {
+        while let Some(&cell) = self.active.last() {
+            let (min, max) = self.grid.cell_bounds(cell);
+
+            if let Some(dart) = self.sample_void(min, max, rng) {
+                self.grid.insert(dart);
+                self.placed += 1;
+                return Some(dart);
+            }
+
+            // The cell's void has been clipped down to nothing (or below the resolution limit):
+            // no further sample can be placed in it, so drop it for good.
+            self.active.pop();
+        }
+
+        None
+    }
+}
+
+impl PoissonDiskAlgorithm<2, Vec2> for Ebeida {
+    fn init<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2> {
+        self.next(rng)
+    }
+
+    fn next<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Vec2> {
+        if !self.phase2 {
+            if let Some(dart) = self.throw_phase1(rng) {
+                return Some(dart);
+            }
+            self.phase2 = true;
+        }
+
+        self.throw_phase2(rng)
+    }
+
+    fn remaining_upper_bound(&self) -> usize {
+        self.grid.cells() - self.placed
+    }
 }
 
 // TODO: GPU, based on compute shaders, maybe
-// TODO: implement Ebeida algo
 // TODO: implement Voronoi iteration, LLoyd's algorithm, relaxation
 // TODO: implement Voronoi noise or something like that
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ebeida_respects_min_distance() {
+        let radius = 0.1;
+        let extent = [Vec2::from_components(0.0, 0.0), Vec2::from_components(1.0, 1.0)];
+        let rng = rand::thread_rng();
+
+        let samples: Vec<Vec2> =
+            PoissonDisk::new(rng, Ebeida::new(radius, extent)).collect();
+
+        assert!(samples.len() > 1);
+
+        for (i, &a) in samples.iter().enumerate() {
+            for &b in &samples[i + 1..] {
+                assert!(
+                    (a - b).length_squared() >= radius * radius,
+                    "{:?} and {:?} are closer than radius {radius}",
+                    a.into_array(),
+                    b.into_array(),
+                );
+            }
+        }
+    }
+}